@@ -0,0 +1,58 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+
+//! Documentation-coverage diagnostics for `deno doc --coverage`. Scans
+//! already-parsed `doc_nodes` for exported symbols missing a JSDoc block
+//! instead of formatting them for display.
+
+use deno_doc as doc;
+
+/// Aggregate documentation coverage across a set of doc nodes.
+pub struct CoverageReport {
+  pub documented: usize,
+  pub total: usize,
+}
+
+impl CoverageReport {
+  pub fn percentage(&self) -> f64 {
+    if self.total == 0 {
+      100.0
+    } else {
+      (self.documented as f64 / self.total as f64) * 100.0
+    }
+  }
+}
+
+/// Reports every exported symbol lacking a JSDoc block to stderr and returns
+/// the aggregate coverage across `doc_nodes`. Import nodes are skipped, since
+/// they're aliases rather than symbols a module actually documents, and so
+/// are non-exported (`Private`) declarations pulled in by `--private` — but
+/// ambient `declare` symbols (how `lib.deno.d.ts` and most `.d.ts` files
+/// write their public API) still count, since `declare` isn't "private".
+pub fn check(doc_nodes: &[doc::DocNode]) -> CoverageReport {
+  let mut total = 0;
+  let mut documented = 0;
+
+  for doc_node in doc_nodes {
+    if doc_node.kind == doc::DocNodeKind::Import {
+      continue;
+    }
+    if doc_node.declaration_kind == doc::DeclarationKind::Private {
+      continue;
+    }
+
+    total += 1;
+    if doc_node.js_doc.is_empty() {
+      eprintln!(
+        "{}:{}:{} - {} is missing documentation",
+        doc_node.location.filename,
+        doc_node.location.line,
+        doc_node.location.col,
+        doc_node.name
+      );
+    } else {
+      documented += 1;
+    }
+  }
+
+  CoverageReport { documented, total }
+}
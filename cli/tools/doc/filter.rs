@@ -0,0 +1,101 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+
+//! Structured filtering for `deno doc`'s output. A filter expression is a
+//! whitespace-separated list of terms: `kind=function,class` restricts to
+//! one or more `DocNodeKind`s, `visibility=public|private` restricts by
+//! declaration visibility, and any other term is treated as the symbol name,
+//! matching the existing `find_nodes_by_name_recursively` behavior. Terms
+//! compose, e.g. `deno doc --filter "kind=class,function"`.
+
+use deno_doc as doc;
+
+#[derive(Debug, Default)]
+pub struct DocFilter {
+  name: Option<String>,
+  kinds: Option<Vec<doc::DocNodeKind>>,
+  private_only: Option<bool>,
+}
+
+impl DocFilter {
+  pub fn parse(expr: &str) -> Self {
+    let mut filter = DocFilter::default();
+    let mut name_terms = Vec::new();
+
+    for term in expr.split_whitespace() {
+      if let Some(kinds) = term.strip_prefix("kind=") {
+        let parsed = kinds.split(',').filter_map(parse_kind).collect::<Vec<_>>();
+        filter.kinds.get_or_insert_with(Vec::new).extend(parsed);
+      } else if let Some(visibility) = term.strip_prefix("visibility=") {
+        match visibility {
+          "private" => filter.private_only = Some(true),
+          "public" => filter.private_only = Some(false),
+          _ => {}
+        }
+      } else {
+        name_terms.push(term);
+      }
+    }
+
+    if !name_terms.is_empty() {
+      filter.name = Some(name_terms.join(" "));
+    } else if filter.kinds.is_none() && filter.private_only.is_none() {
+      // Nothing matched a `kind=`/`visibility=` term, so fall back to the
+      // pre-existing behavior of treating the whole expression as a name,
+      // including an empty one (which `find_nodes_by_name_recursively`
+      // simply won't find).
+      filter.name = Some(expr.to_string());
+    }
+
+    filter
+  }
+
+  /// Applies this filter to `doc_nodes`, returning the subset that matches
+  /// every term that was specified.
+  pub fn apply(&self, doc_nodes: Vec<doc::DocNode>) -> Vec<doc::DocNode> {
+    let mut nodes = doc_nodes;
+
+    if let Some(kinds) = &self.kinds {
+      nodes.retain(|doc_node| kinds.contains(&doc_node.kind));
+    }
+
+    if let Some(private_only) = self.private_only {
+      nodes.retain(|doc_node| {
+        (doc_node.declaration_kind == doc::DeclarationKind::Private)
+          == private_only
+      });
+    }
+
+    if let Some(name) = &self.name {
+      nodes = doc::find_nodes_by_name_recursively(nodes, name.clone());
+    }
+
+    nodes
+  }
+
+  /// A message describing what this filter was looking for, for use when
+  /// `apply` returns no nodes. Distinguishes a `kind=`/`visibility=` filter
+  /// that legitimately matched nothing from a plain name lookup, so e.g. a
+  /// module with no enums doesn't read as if `kind=enum` failed to parse.
+  pub fn not_found_message(&self, expr: &str) -> String {
+    match (&self.name, self.kinds.is_some() || self.private_only.is_some()) {
+      (Some(name), false) => format!("Node {} was not found!", name),
+      (Some(name), true) => {
+        format!("No symbols named \"{}\" matched filter \"{}\"", name, expr)
+      }
+      (None, _) => format!("No symbols matched filter \"{}\"", expr),
+    }
+  }
+}
+
+fn parse_kind(kind: &str) -> Option<doc::DocNodeKind> {
+  match kind.trim() {
+    "function" => Some(doc::DocNodeKind::Function),
+    "variable" => Some(doc::DocNodeKind::Variable),
+    "class" => Some(doc::DocNodeKind::Class),
+    "enum" => Some(doc::DocNodeKind::Enum),
+    "interface" => Some(doc::DocNodeKind::Interface),
+    "typeAlias" => Some(doc::DocNodeKind::TypeAlias),
+    "namespace" => Some(doc::DocNodeKind::Namespace),
+    _ => None,
+  }
+}
@@ -0,0 +1,330 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+
+//! A static HTML site generator for `deno doc --html`. Takes the same
+//! `doc_nodes` that would otherwise be handed to `DocPrinter` and instead
+//! renders them to a directory of cross-linked HTML pages: an `index.html`
+//! grouping symbols by `DocNodeKind`, and one page per distinct
+//! (originating specifier, symbol name) pair (overloads share a page, the
+//! way the terminal `DocPrinter` renders them together).
+
+use deno_core::error::AnyError;
+use deno_doc as doc;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Identifies a page: the specifier a symbol was declared in, plus its name.
+/// Keying on the specifier too (not just the name) keeps unrelated same-named
+/// symbols from different entrypoints (`parse`, `Client`, ...) from being
+/// merged onto one page when documenting a package with several entrypoints.
+type GroupKey = (String, String);
+
+/// Renders `doc_nodes` as a static site into `output_dir`, creating it (and
+/// any missing parents) if necessary.
+pub fn generate(
+  doc_nodes: Vec<doc::DocNode>,
+  output_dir: &Path,
+  private: bool,
+) -> Result<(), AnyError> {
+  let mut doc_nodes = doc_nodes;
+  doc_nodes.retain(|doc_node| doc_node.kind != doc::DocNodeKind::Import);
+
+  fs::create_dir_all(output_dir)?;
+
+  // Symbols can be overloaded (functions, methods, `Deno.open`-style
+  // namespaces in `lib.deno.d.ts`), so group same-named nodes from the same
+  // specifier onto a single page instead of writing one page per node.
+  let mut groups: BTreeMap<GroupKey, Vec<doc::DocNode>> = BTreeMap::new();
+  for node in doc_nodes {
+    let key = (node.location.filename.clone(), node.name.clone());
+    groups.entry(key).or_default().push(node);
+  }
+
+  let pages = assign_pages(groups.keys());
+
+  let mut by_kind: BTreeMap<&'static str, Vec<&GroupKey>> = BTreeMap::new();
+  for (key, nodes) in &groups {
+    by_kind.entry(kind_label(&nodes[0].kind)).or_default().push(key);
+  }
+
+  let mut index = String::new();
+  index.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">");
+  index.push_str("<title>Documentation</title></head><body>\n");
+  index.push_str("<h1>Documentation</h1>\n");
+  for (kind, keys) in &by_kind {
+    index.push_str(&format!("<h2>{}</h2>\n<ul>\n", kind));
+    for key in keys {
+      let (specifier, name) = key;
+      index.push_str(&format!(
+        "<li><a href=\"{}\">{}</a> <small>{}</small></li>\n",
+        pages[*key].display(),
+        html_escape(name),
+        html_escape(specifier)
+      ));
+    }
+    index.push_str("</ul>\n");
+  }
+  index.push_str("</body></html>\n");
+  fs::write(output_dir.join("index.html"), index)?;
+
+  for (key, nodes) in &groups {
+    let body = render_symbol_page(key, nodes, &pages, private);
+    fs::write(output_dir.join(&pages[key]), body)?;
+  }
+
+  Ok(())
+}
+
+fn kind_label(kind: &doc::DocNodeKind) -> &'static str {
+  match kind {
+    doc::DocNodeKind::Function => "Functions",
+    doc::DocNodeKind::Variable => "Variables",
+    doc::DocNodeKind::Class => "Classes",
+    doc::DocNodeKind::Enum => "Enums",
+    doc::DocNodeKind::Interface => "Interfaces",
+    doc::DocNodeKind::TypeAlias => "Type Aliases",
+    doc::DocNodeKind::Namespace => "Namespaces",
+    doc::DocNodeKind::Import => "Imports",
+  }
+}
+
+/// Assigns each key a unique page path. Blanking non-alphanumeric characters
+/// to build a readable slug means distinct keys can collide (`_foo` and
+/// `$foo`, `foo-bar` and `foo_bar`, ...), so collisions get a numeric suffix
+/// rather than silently overwriting an earlier page. Iterating a `BTreeMap`'s
+/// keys is stable, so the same input always gets the same suffixes.
+fn assign_pages<'a>(
+  keys: impl Iterator<Item = &'a GroupKey>,
+) -> BTreeMap<GroupKey, PathBuf> {
+  let mut pages = BTreeMap::new();
+  let mut used = std::collections::HashSet::new();
+
+  for key in keys {
+    let base_slug = page_slug(key);
+    let mut path = PathBuf::from(format!("{}.html", base_slug));
+    let mut suffix = 2;
+    while !used.insert(path.clone()) {
+      path = PathBuf::from(format!("{}_{}.html", base_slug, suffix));
+      suffix += 1;
+    }
+    pages.insert(key.clone(), path);
+  }
+
+  pages
+}
+
+fn page_slug(key: &GroupKey) -> String {
+  let (specifier, name) = key;
+  format!("{}_{}", specifier, name)
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+    .collect()
+}
+
+fn render_symbol_page(
+  key: &GroupKey,
+  nodes: &[doc::DocNode],
+  pages: &BTreeMap<GroupKey, PathBuf>,
+  private: bool,
+) -> String {
+  let (specifier, name) = key;
+
+  // Reuse the existing terminal printer to produce the signature and JSDoc
+  // prose for this symbol (and all its overloads), rather than
+  // reimplementing formatting here.
+  let signature =
+    format!("{}", doc::DocPrinter::new(nodes, false, private));
+
+  let mut body = String::new();
+  body.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">");
+  body.push_str(&format!(
+    "<title>{}</title></head><body>\n",
+    html_escape(name)
+  ));
+  body.push_str("<p><a href=\"index.html\">&laquo; index</a></p>\n");
+  body.push_str(&format!("<h1>{}</h1>\n", html_escape(name)));
+  body.push_str(&format!(
+    "<p><small>{}</small></p>\n",
+    html_escape(specifier)
+  ));
+  body.push_str("<pre>");
+  body.push_str(&html_escape(&signature));
+  body.push_str("</pre>\n");
+
+  let related = related_symbols(specifier, name, nodes, pages);
+  if !related.is_empty() {
+    body.push_str("<h2>See also</h2>\n<ul>\n");
+    for related_key in related {
+      body.push_str(&format!(
+        "<li><a href=\"{}\">{}</a></li>\n",
+        pages[&related_key].display(),
+        html_escape(&related_key.1)
+      ));
+    }
+    body.push_str("</ul>\n");
+  }
+
+  body.push_str("</body></html>\n");
+  body
+}
+
+/// Returns the pages of other documented symbols that `nodes` actually
+/// reference in their structured type information (params, return types,
+/// `extends`/`implements`, etc.), resolved the same way the doc graph
+/// resolved them in the first place, rather than by pattern-matching the
+/// rendered signature text. A referenced name is preferred from `self_name`'s
+/// own specifier; if it's not there and resolves to more than one other
+/// specifier, it's left unlinked rather than guessing the wrong one.
+fn related_symbols(
+  self_specifier: &str,
+  self_name: &str,
+  nodes: &[doc::DocNode],
+  pages: &BTreeMap<GroupKey, PathBuf>,
+) -> Vec<GroupKey> {
+  let mut seen = std::collections::BTreeSet::new();
+  for node in nodes {
+    for type_ref in collect_node_type_refs(node) {
+      if type_ref == self_name {
+        continue;
+      }
+      if let Some(key) = resolve_page(pages, self_specifier, &type_ref) {
+        seen.insert(key);
+      }
+    }
+  }
+  seen.into_iter().collect()
+}
+
+fn resolve_page(
+  pages: &BTreeMap<GroupKey, PathBuf>,
+  preferred_specifier: &str,
+  name: &str,
+) -> Option<GroupKey> {
+  let preferred = (preferred_specifier.to_string(), name.to_string());
+  if pages.contains_key(&preferred) {
+    return Some(preferred);
+  }
+
+  let mut matches =
+    pages.keys().filter(|(_, candidate_name)| candidate_name == name);
+  let only_match = matches.next()?;
+  if matches.next().is_some() {
+    None
+  } else {
+    Some(only_match.clone())
+  }
+}
+
+fn collect_node_type_refs(node: &doc::DocNode) -> Vec<String> {
+  let mut refs = Vec::new();
+
+  if let Some(function_def) = &node.function_def {
+    collect_function_refs(function_def, &mut refs);
+  }
+  if let Some(variable_def) = &node.variable_def {
+    if let Some(ts_type) = &variable_def.ts_type {
+      collect_ts_type_refs(ts_type, &mut refs);
+    }
+  }
+  if let Some(class_def) = &node.class_def {
+    if let Some(extends) = &class_def.extends {
+      refs.push(extends.clone());
+    }
+    for ts_type in &class_def.implements {
+      collect_ts_type_refs(ts_type, &mut refs);
+    }
+    for method in &class_def.methods {
+      collect_function_refs(&method.function_def, &mut refs);
+    }
+    for property in &class_def.properties {
+      if let Some(ts_type) = &property.ts_type {
+        collect_ts_type_refs(ts_type, &mut refs);
+      }
+    }
+  }
+  if let Some(interface_def) = &node.interface_def {
+    for ts_type in &interface_def.extends {
+      collect_ts_type_refs(ts_type, &mut refs);
+    }
+    for method in &interface_def.methods {
+      for param in &method.params {
+        if let Some(ts_type) = &param.ts_type {
+          collect_ts_type_refs(ts_type, &mut refs);
+        }
+      }
+      if let Some(return_type) = &method.return_type {
+        collect_ts_type_refs(return_type, &mut refs);
+      }
+    }
+    for property in &interface_def.properties {
+      if let Some(ts_type) = &property.ts_type {
+        collect_ts_type_refs(ts_type, &mut refs);
+      }
+    }
+  }
+  if let Some(type_alias_def) = &node.type_alias_def {
+    collect_ts_type_refs(&type_alias_def.ts_type, &mut refs);
+  }
+
+  refs
+}
+
+fn collect_function_refs(
+  function_def: &doc::FunctionDef,
+  refs: &mut Vec<String>,
+) {
+  for param in &function_def.params {
+    if let Some(ts_type) = &param.ts_type {
+      collect_ts_type_refs(ts_type, refs);
+    }
+  }
+  if let Some(return_type) = &function_def.return_type {
+    collect_ts_type_refs(return_type, refs);
+  }
+}
+
+fn collect_ts_type_refs(ts_type: &doc::TsTypeDef, refs: &mut Vec<String>) {
+  if let Some(type_ref) = &ts_type.type_ref {
+    refs.push(type_ref.type_name.clone());
+    if let Some(type_params) = &type_ref.type_params {
+      for type_param in type_params {
+        collect_ts_type_refs(type_param, refs);
+      }
+    }
+  }
+  if let Some(union) = &ts_type.union {
+    for member in union {
+      collect_ts_type_refs(member, refs);
+    }
+  }
+  if let Some(intersection) = &ts_type.intersection {
+    for member in intersection {
+      collect_ts_type_refs(member, refs);
+    }
+  }
+  if let Some(array) = &ts_type.array {
+    collect_ts_type_refs(array, refs);
+  }
+  if let Some(tuple) = &ts_type.tuple {
+    for member in tuple {
+      collect_ts_type_refs(member, refs);
+    }
+  }
+  if let Some(parenthesized) = &ts_type.parenthesized {
+    collect_ts_type_refs(parenthesized, refs);
+  }
+  if let Some(rest) = &ts_type.rest {
+    collect_ts_type_refs(rest, refs);
+  }
+  if let Some(optional) = &ts_type.optional {
+    collect_ts_type_refs(optional, refs);
+  }
+}
+
+fn html_escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+}
@@ -1,5 +1,9 @@
 // Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
 
+mod coverage;
+mod filter;
+mod html;
+
 use crate::colors;
 use crate::file_fetcher::File;
 use crate::flags::Flags;
@@ -92,16 +96,18 @@ impl Loader for DocLoader {
 
 pub async fn print_docs(
   flags: Flags,
-  source_file: Option<String>,
+  source_files: Vec<String>,
   json: bool,
   maybe_filter: Option<String>,
+  maybe_html_output: Option<PathBuf>,
+  coverage: bool,
+  maybe_coverage_threshold: Option<u8>,
   private: bool,
 ) -> Result<(), AnyError> {
   let program_state = ProgramState::build(flags.clone()).await?;
-  let source_file = source_file.unwrap_or_else(|| "--builtin".to_string());
   let source_parser = deno_graph::DefaultSourceParser::new();
 
-  let parse_result = if source_file == "--builtin" {
+  let parse_result = if source_files.is_empty() {
     let mut loader = StubDocLoader;
     let source_file_specifier =
       ModuleSpecifier::parse("deno://lib.deno.d.ts").unwrap();
@@ -120,16 +126,28 @@ pub async fn print_docs(
       Arc::new(get_types(flags.unstable)),
     )
   } else {
-    let module_specifier = resolve_url_or_path(&source_file)?;
+    let module_specifiers = source_files
+      .iter()
+      .map(|source_file| resolve_url_or_path(source_file))
+      .collect::<Result<Vec<_>, _>>()?;
 
     // If the root module has external types, the module graph won't redirect it,
-    // so instead create a dummy file which exports everything from the actual file being documented.
+    // so instead create a dummy file which re-exports everything from each of
+    // the actual files being documented, merging all entrypoints into a
+    // single doc graph.
     let root_specifier = resolve_url_or_path("./$deno$doc.ts").unwrap();
+    let root_source = module_specifiers
+      .iter()
+      .map(|module_specifier| {
+        format!("export * from \"{}\";", module_specifier)
+      })
+      .collect::<Vec<_>>()
+      .join("\n");
     let root = File {
       local: PathBuf::from("./$deno$doc.ts"),
       maybe_types: None,
       media_type: MediaType::TypeScript,
-      source: Arc::new(format!("export * from \"{}\";", module_specifier)),
+      source: Arc::new(root_source),
       specifier: root_specifier.clone(),
       maybe_headers: None,
     };
@@ -163,28 +181,104 @@ pub async fn print_docs(
     }
   };
 
+  // Multiple entrypoints can both `export *` the same underlying module, so
+  // de-duplicate symbols that resolve to the same specifier/name pair. The
+  // position is part of the key too, since overloaded declarations (multiple
+  // `function open(...)` signatures, merged namespace members, etc.)
+  // legitimately share a specifier and name but live at distinct locations.
+  if source_files.len() > 1 {
+    let mut seen = std::collections::HashSet::new();
+    doc_nodes.retain(|doc_node| {
+      seen.insert((
+        doc_node.location.filename.clone(),
+        doc_node.name.clone(),
+        doc_node.location.line,
+        doc_node.location.col,
+      ))
+    });
+  }
+
+  let multi_entrypoint = source_files.len() > 1;
+
+  if coverage {
+    let report = coverage::check(&doc_nodes);
+    let percentage = report.percentage();
+    eprintln!(
+      "Documentation coverage: {:.1}% ({}/{})",
+      percentage, report.documented, report.total
+    );
+    if let Some(threshold) = maybe_coverage_threshold {
+      if percentage < threshold as f64 {
+        std::process::exit(1);
+      }
+    }
+    return Ok(());
+  }
+
   if json {
+    let doc_nodes = apply_filter_or_exit(doc_nodes, &maybe_filter);
     write_json_to_stdout(&doc_nodes)
+  } else if let Some(html_output) = maybe_html_output {
+    html::generate(doc_nodes, &html_output, private)
   } else {
     doc_nodes.retain(|doc_node| doc_node.kind != doc::DocNodeKind::Import);
-    let details = if let Some(filter) = maybe_filter {
-      let nodes =
-        doc::find_nodes_by_name_recursively(doc_nodes, filter.clone());
-      if nodes.is_empty() {
-        eprintln!("Node {} was not found!", filter);
-        std::process::exit(1);
-      }
-      format!(
-        "{}",
-        doc::DocPrinter::new(&nodes, colors::use_color(), private)
-      )
-    } else {
-      format!(
-        "{}",
-        doc::DocPrinter::new(&doc_nodes, colors::use_color(), private)
-      )
-    };
-
+    let doc_nodes = apply_filter_or_exit(doc_nodes, &maybe_filter);
+    let details = format_doc_nodes(&doc_nodes, multi_entrypoint, private);
     write_to_stdout_ignore_sigpipe(details.as_bytes()).map_err(AnyError::from)
   }
 }
+
+/// Applies `maybe_filter` (if any) to `doc_nodes`, exiting with an error if a
+/// filter was given but matched nothing.
+fn apply_filter_or_exit(
+  doc_nodes: Vec<doc::DocNode>,
+  maybe_filter: &Option<String>,
+) -> Vec<doc::DocNode> {
+  let filter_expr = match maybe_filter {
+    Some(filter_expr) => filter_expr,
+    None => return doc_nodes,
+  };
+
+  let filter = filter::DocFilter::parse(filter_expr);
+  let nodes = filter.apply(doc_nodes);
+  if nodes.is_empty() {
+    eprintln!("{}", filter.not_found_message(filter_expr));
+    std::process::exit(1);
+  }
+  nodes
+}
+
+/// Formats `doc_nodes` for terminal output. When documenting more than one
+/// entrypoint, symbols are grouped under a heading naming the specifier they
+/// originated from so the merged output stays attributable.
+fn format_doc_nodes(
+  doc_nodes: &[doc::DocNode],
+  multi_entrypoint: bool,
+  private: bool,
+) -> String {
+  if !multi_entrypoint {
+    return format!(
+      "{}",
+      doc::DocPrinter::new(doc_nodes, colors::use_color(), private)
+    );
+  }
+
+  let mut by_specifier: std::collections::BTreeMap<String, Vec<doc::DocNode>> =
+    std::collections::BTreeMap::new();
+  for doc_node in doc_nodes {
+    by_specifier
+      .entry(doc_node.location.filename.clone())
+      .or_default()
+      .push(doc_node.clone());
+  }
+
+  let mut output = String::new();
+  for (specifier, nodes) in &by_specifier {
+    output.push_str(&format!("{}\n", colors::bold(specifier)));
+    output.push_str(&format!(
+      "{}\n",
+      doc::DocPrinter::new(nodes, colors::use_color(), private)
+    ));
+  }
+  output
+}